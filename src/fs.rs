@@ -0,0 +1,54 @@
+//! Serve static files and directories as part of a route tree, the same way
+//! `tide::Route::serve_dir`/`serve_file` do for native Tide routes.
+
+use std::path::{Component, Path as StdPath, PathBuf};
+
+use crate::routebuilder::RouteBuilder;
+use crate::Result;
+
+/// Extends `RouteBuilder` with combinators for serving static files and directories.
+pub trait ServeFs<State: Clone + Send + Sync + 'static>: RouteBuilder<State> {
+    /// Serve the directory at `dir` under a wildcard below the current path, the same way
+    /// `tide::Route::serve_dir` does.
+    fn serve_dir(self, dir: impl AsRef<StdPath>) -> Result<Self>;
+
+    /// Serve a single file at `file` at the current path.
+    fn serve_file(self, file: impl AsRef<StdPath>) -> Result<Self>;
+}
+
+impl<State: Clone + Send + Sync + 'static, T: RouteBuilder<State>> ServeFs<State> for T {
+    fn serve_dir(self, dir: impl AsRef<StdPath>) -> Result<Self> {
+        let dir = dir.as_ref().to_owned().canonicalize()?;
+
+        Ok(self.at("*path", move |route| {
+            let dir = dir.clone();
+            route.get(move |request: tide::Request<State>| {
+                let dir = dir.clone();
+                async move {
+                    let path = request.param("path")?;
+                    let mut file_path = dir.clone();
+                    for segment in StdPath::new(path).components() {
+                        if let Component::Normal(segment) = segment {
+                            file_path.push(segment);
+                        }
+                    }
+
+                    let body = tide::Body::from_file(&file_path).await?;
+                    Ok(tide::Response::builder(tide::StatusCode::Ok).body(body).build())
+                }
+            })
+        }))
+    }
+
+    fn serve_file(self, file: impl AsRef<StdPath>) -> Result<Self> {
+        let file: PathBuf = file.as_ref().to_owned();
+
+        Ok(self.get(move |_: tide::Request<State>| {
+            let file = file.clone();
+            async move {
+                let body = tide::Body::from_file(&file).await?;
+                Ok(tide::Response::builder(tide::StatusCode::Ok).body(body).build())
+            }
+        }))
+    }
+}
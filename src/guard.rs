@@ -0,0 +1,128 @@
+//! Conditionally short-circuit a scope of the route tree, the way Tide's own "around" middleware
+//! design allows a middleware to return a response immediately instead of calling `next.run()`.
+//! `.guard(...)` exposes this directly in the fluent api: a predicate runs before anything in the
+//! guarded subtree, and if it returns `false` the chain short-circuits with a configurable status
+//! without ever invoking the endpoint.
+
+use tide::{Middleware, Next, Request, Response, Result, StatusCode};
+
+use crate::routebuilder::RouteBuilder;
+use crate::routesegment::SubRoute;
+
+/// Extends `RouteBuilder` with `.guard`, a combinator that wraps a subtree so a predicate is
+/// checked before any of its endpoints run.
+pub trait Guard<State: Clone + Send + Sync + 'static>: RouteBuilder<State> {
+    /// Only let requests into the subtree built by `routes` when `predicate` returns `true`;
+    /// otherwise short-circuit with a `403 Forbidden` response.
+    fn guard<F>(self, predicate: F, routes: impl FnOnce(SubRoute<State>) -> SubRoute<State>) -> Self
+    where
+        F: Fn(&Request<State>) -> bool + Send + Sync + 'static,
+    {
+        self.guard_with_status(StatusCode::Forbidden, predicate, routes)
+    }
+
+    /// Like `guard`, but with a configurable status for the short-circuit response.
+    fn guard_with_status<F>(
+        self,
+        status: StatusCode,
+        predicate: F,
+        routes: impl FnOnce(SubRoute<State>) -> SubRoute<State>,
+    ) -> Self
+    where
+        F: Fn(&Request<State>) -> bool + Send + Sync + 'static;
+}
+
+impl<State: Clone + Send + Sync + 'static, T: RouteBuilder<State>> Guard<State> for T {
+    fn guard_with_status<F>(
+        self,
+        status: StatusCode,
+        predicate: F,
+        routes: impl FnOnce(SubRoute<State>) -> SubRoute<State>,
+    ) -> Self
+    where
+        F: Fn(&Request<State>) -> bool + Send + Sync + 'static,
+    {
+        self.with(GuardMiddleware { status, predicate }, routes)
+    }
+}
+
+struct GuardMiddleware<F> {
+    status: StatusCode,
+    predicate: F,
+}
+
+impl<F> std::fmt::Debug for GuardMiddleware<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GuardMiddleware")
+            .field("status", &self.status)
+            .field("predicate", &"..")
+            .finish()
+    }
+}
+
+#[tide::utils::async_trait]
+impl<State, F> Middleware<State> for GuardMiddleware<F>
+where
+    State: Clone + Send + Sync + 'static,
+    F: Fn(&Request<State>) -> bool + Send + Sync + 'static,
+{
+    async fn handle(&self, request: Request<State>, next: Next<'_, State>) -> Result {
+        if (self.predicate)(&request) {
+            Ok(next.run(request).await)
+        } else {
+            Ok(Response::new(self.status))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[async_std::test]
+    async fn guard_short_circuits_when_predicate_is_false() {
+        let mut server = tide::Server::new();
+        server
+            .register(root::<()>().at("admin", |route| {
+                route.guard(
+                    |request| request.header("x-admin").is_some(),
+                    |route| route.get(|_| async { Ok("") }),
+                )
+            }))
+            .unwrap();
+
+        let response: tide::http::Response = server
+            .respond(tide::http::Request::new(
+                Method::Get,
+                tide::http::Url::parse("http://example.com/admin").unwrap(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::Forbidden);
+    }
+
+    #[async_std::test]
+    async fn guard_lets_requests_through_when_predicate_is_true() {
+        let mut server = tide::Server::new();
+        server
+            .register(root::<()>().at("admin", |route| {
+                route.guard(
+                    |request| request.header("x-admin").is_some(),
+                    |route| route.get(|_| async { Ok("") }),
+                )
+            }))
+            .unwrap();
+
+        let mut request = tide::http::Request::new(
+            Method::Get,
+            tide::http::Url::parse("http://example.com/admin").unwrap(),
+        );
+        request.insert_header("x-admin", "true");
+
+        let response: tide::http::Response = server.respond(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::Ok);
+    }
+}
@@ -3,7 +3,9 @@
 //! route-tree and you can integrate endpoints.
 //! Some things that are possible with Tide-native routes are not (yet) possible;
 //! - Tide prefix routes are not implemented
-//! - you can not nest Tide servers
+//!
+//! Nested `tide::Server`s can be mounted with `serve_server` from the `nest` module, composing
+//! independently-built sub-applications into one route tree.
 //!
 //! To use this you can import Tide Fluent Routes with `use tide_fluent_routes::prelude::*` it
 //! introduces the `register` extension method on the `Tide::Server` to register routes from a
@@ -120,9 +122,31 @@
 //!         )
 //!         .at("api/v2", |route| route
 //!             .get(endpoint)
-//!             .get(endpoint)
+//!             .post(endpoint)
 //!         ),
-//! );
+//! ).expect("Error setting up routes");
+//! ```
+//!
+//! Routes can be given a name with `.name(...)` so you don't have to hardcode their path
+//! elsewhere; a `ReverseRouter` collects named routes while the tree is registered on it and
+//! renders a concrete url for one with `url_for`, substituting its `:param` segments.
+//! ```rust
+//! # use tide::{Request, Result};
+//! # use tide_fluent_routes::prelude::*;
+//! # async fn endpoint(_: Request<()>) -> Result {
+//! #     todo!()
+//! # }
+//! let routes = root::<()>()
+//!     .at("articles", |route| route
+//!         .at(":id", |route| route.get(endpoint).name("article.show"))
+//!     );
+//!
+//! let mut reverse_router = ReverseRouter::new();
+//! reverse_router.register(routes).expect("Error setting up routes");
+//!
+//! let mut params = std::collections::HashMap::new();
+//! params.insert("id", "42");
+//! assert_eq!(reverse_router.url_for("article.show", &params).unwrap(), "/articles/42");
 //! ```
 //!
 //! Serving directories is possible using `serve_dir`, this works the same as with normal Tide routes,
@@ -140,7 +164,46 @@
 //!         .at("img", |r| r
 //!             .serve_dir("files/images").unwrap()
 //!         )
-//! );
+//! ).expect("Error setting up routes");
+//! ```
+//!
+//! `register` validates the route tree before registering it, so conflicts that would otherwise
+//! only surface later as confusing behavior inside Tide's own router - the same method registered
+//! twice for a path, sibling branches using different names for the same wildcard position, or a
+//! catch-all that isn't the last segment of its path - fail fast with a descriptive error instead.
+//! You can run the same validation without registering anything with `try_build`;
+//! ```rust
+//! # use tide::{Request, Result};
+//! # use tide_fluent_routes::prelude::*;
+//! # async fn endpoint(_: Request<()>) -> Result {
+//! #     todo!()
+//! # }
+//! let conflicting = root::<()>()
+//!     .at("articles", |route| route.get(endpoint))
+//!     .at("articles", |route| route.get(endpoint));
+//!
+//! assert!(conflicting.try_build().is_err());
+//! ```
+//!
+//! `.guard(...)` short-circuits a subtree of the route tree when a predicate returns `false`,
+//! without ever invoking the endpoints inside it; `.guard_with_status` lets you pick the status
+//! for the short-circuit response instead of the default `403 Forbidden`.
+//! ```rust
+//! # use tide::{Request, Result};
+//! # use tide_fluent_routes::prelude::*;
+//! use tide_fluent_routes::guard::Guard;
+//! # async fn endpoint(_: Request<()>) -> Result {
+//! #     todo!()
+//! # }
+//! # let mut server = tide::Server::new();
+//! server.register(
+//!     root()
+//!         .at("admin", |route| route
+//!             .guard(|request| request.header("x-admin").is_some(), |route| route
+//!                 .get(endpoint)
+//!             )
+//!         )
+//! ).expect("Error setting up routes");
 //! ```
 
 // Turn on warnings for some lints
@@ -155,14 +218,15 @@
 )]
 
 pub mod fs;
+pub mod guard;
+pub mod nest;
 mod path;
 pub mod reverse_router;
 pub mod routebuilder;
 pub mod router;
 mod routesegment;
-mod util;
+mod validate;
 
-use std::collections::HashMap;
 pub use tide::Error;
 
 /// The result type for fluent routing
@@ -180,14 +244,13 @@ pub mod prelude {
 #[cfg(test)]
 mod test {
     use crate::prelude::*;
-    use crate::util::ArcMiddleware;
     use std::future::Future;
     use std::pin::Pin;
     use tide::{Next, Request, Result};
 
     #[test]
     fn should_build_single_endpoint() {
-        let routes: Vec<_> = root::<()>().get(|_| async { Ok("") }).unwrap().build();
+        let routes: Vec<_> = root::<()>().get(|_| async { Ok("") }).build();
 
         assert_eq!(routes.len(), 1);
     }
@@ -197,7 +260,6 @@ mod test {
         let routes: Vec<_> = root::<()>()
             .get(|_| async { Ok("") })
             .post(|_| async { Ok("") })
-            .unwrap()
             .build();
 
         assert_eq!(routes.len(), 2);
@@ -209,7 +271,6 @@ mod test {
             .at("sub_path", |r| {
                 r.get(|_| async { Ok("") }).post(|_| async { Ok("") })
             })
-            .unwrap()
             .build();
 
         assert_eq!(routes.len(), 2);
@@ -219,7 +280,6 @@ mod test {
     fn should_build_endpoint_path() {
         let routes: Vec<_> = root::<()>()
             .at("path", |r| r.at("subpath", |r| r.get(|_| async { Ok("") })))
-            .unwrap()
             .build();
 
         assert_eq!(routes.len(), 1);
@@ -233,7 +293,7 @@ mod test {
 
     #[test]
     fn should_start_path_with_slash() {
-        let routes: Vec<_> = root::<()>().get(|_| async { Ok("") }).unwrap().build();
+        let routes: Vec<_> = root::<()>().get(|_| async { Ok("") }).build();
         assert_eq!(routes.get(0).unwrap().path.to_string(), "/".to_string());
     }
 
@@ -246,19 +306,13 @@ mod test {
 
     #[test]
     fn should_collect_middleware() {
-        let middleware1 = ArcMiddleware::new(middleware);
-        let middleware2 = ArcMiddleware::new(middleware);
-
         let routes: Vec<_> = root::<()>()
             .at("path", |r| {
-                r.with(middleware1.clone(), |r| {
-                    r.at("subpath", |r| {
-                        r.with(middleware2.clone(), |r| r.get(|_| async { Ok("") }))
-                    })
-                    .get(|_| async { Ok("") })
+                r.with(middleware, |r| {
+                    r.at("subpath", |r| r.with(middleware, |r| r.get(|_| async { Ok("") })))
+                        .get(|_| async { Ok("") })
                 })
             })
-            .unwrap()
             .build();
 
         assert_eq!(routes.get(0).unwrap().middleware.len(), 1);
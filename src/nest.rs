@@ -0,0 +1,73 @@
+//! Mount an independently-built `tide::Server` as a subtree of the route tree, the way `fs`
+//! mounts a static directory: the current path becomes a wildcard prefix, the matched remainder
+//! is rewritten onto the request and the nested server's `respond` is invoked directly. This
+//! makes it possible to compose independently-built sub-applications, each with their own
+//! middleware stack and state, into one route tree.
+
+use crate::routebuilder::RouteBuilder;
+
+const MOUNT_PARAM: &str = "tide_fluent_routes_mount_path";
+
+/// Extends `RouteBuilder` with the ability to mount a nested `tide::Server` as a subtree.
+pub trait Nest<State: Clone + Send + Sync + 'static>: RouteBuilder<State> {
+    /// Mount `server` under the current path. Every request whose path falls under it is
+    /// forwarded to `server`, with the matched prefix stripped off so `server` sees the request
+    /// the same way it would if it were handling it at its own root.
+    fn serve_server(self, server: tide::Server<State>) -> Self;
+}
+
+impl<State: Clone + Send + Sync + 'static, T: RouteBuilder<State>> Nest<State> for T {
+    fn serve_server(self, server: tide::Server<State>) -> Self {
+        let wildcard = format!("*{}", MOUNT_PARAM);
+
+        self.at(&wildcard, move |route| {
+            route.all(move |mut request: tide::Request<State>| {
+                let server = server.clone();
+                async move {
+                    let remainder = request.param(MOUNT_PARAM).unwrap_or("").to_string();
+
+                    let mut url = request.url().clone();
+                    url.set_path(&format!("/{}", remainder));
+
+                    let mut inner = tide::http::Request::new(request.method(), url);
+                    for (name, values) in request.iter() {
+                        for value in values {
+                            inner.append_header(name, value.to_owned());
+                        }
+                    }
+                    inner.set_body(request.take_body());
+
+                    let response: tide::http::Response = server.respond(inner).await?;
+                    Ok(tide::Response::from(response))
+                }
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[async_std::test]
+    async fn mounted_server_receives_rewritten_path() {
+        let mut inner = tide::Server::new();
+        inner.at("/hello").get(|_| async { Ok("hello from inner") });
+
+        let mut outer = tide::Server::new();
+        outer
+            .register(root::<()>().at("api", |route| route.serve_server(inner)))
+            .unwrap();
+
+        let mut response: tide::http::Response = outer
+            .respond(tide::http::Request::new(
+                Method::Get,
+                tide::http::Url::parse("http://example.com/api/hello").unwrap(),
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(response.take_body().into_string().await.unwrap(), "hello from inner");
+    }
+}
@@ -0,0 +1,54 @@
+//! Internal representation of a route path template, built up incrementally as a `RouteSegment`
+//! tree is assembled and rendered back out once routes are registered.
+
+use std::fmt;
+
+/// A path template made up of individual `/`-separated segments (literals, `:params` or
+/// `*catch_all` segments). An empty `Path` renders as `/`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct Path {
+    segments: Vec<String>,
+}
+
+impl Path {
+    /// The empty path, representing the root "/"
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append one or more `/`-separated segments, returning the resulting path. Empty segments
+    /// (leading/trailing/duplicate slashes) are ignored, so `"api/v1"` and `"/api/v1/"` behave
+    /// the same.
+    pub(crate) fn append(&self, path: &str) -> Self {
+        let mut segments = self.segments.clone();
+        segments.extend(path.split('/').filter(|segment| !segment.is_empty()).map(String::from));
+        Self { segments }
+    }
+
+    /// The individual segments that make up this path, in order.
+    pub(crate) fn segments(&self) -> &[String] {
+        &self.segments
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "/{}", self.segments.join("/"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn empty_path_renders_as_root() {
+        assert_eq!(Path::new().to_string(), "/");
+    }
+
+    #[test]
+    fn append_joins_and_ignores_empty_segments() {
+        let path = Path::new().append("api/v1").append("/articles/");
+        assert_eq!(path.to_string(), "/api/v1/articles");
+    }
+}
@@ -0,0 +1,133 @@
+//! A `ReverseRouter` collects the routes registered on it, including the ones tagged with
+//! `.name(...)` while building the route tree, so concrete urls can be rendered for them later
+//! with `url_for` instead of hardcoding paths that can drift from the route tree.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tide::{http::Method, Endpoint, Middleware};
+
+use crate::router::Router;
+use crate::Result;
+
+/// Collects the routes registered through it, and the names given to them, so a concrete url can
+/// be rendered for a named route with `url_for`.
+#[derive(Debug, Default)]
+pub struct ReverseRouter {
+    routes: Vec<(String, Option<Method>)>,
+    named_routes: HashMap<String, String>,
+}
+
+impl ReverseRouter {
+    /// Create a new, empty `ReverseRouter`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The routes registered on this `ReverseRouter` so far, as `(path, method)` pairs.
+    pub fn routes(&self) -> &[(String, Option<Method>)] {
+        &self.routes
+    }
+
+    /// Render a concrete url for the route named `name`, substituting each `:param` (and
+    /// catch-all `*param`) segment of its path template with the matching entry from `params`.
+    /// Fails if `name` is unknown, a required parameter is missing, or `params` contains a
+    /// parameter the route doesn't have.
+    pub fn url_for(&self, name: &str, params: &HashMap<&str, &str>) -> Result<String> {
+        let template = self.named_routes.get(name).ok_or_else(|| {
+            tide::Error::from_str(
+                tide::StatusCode::NotFound,
+                format!("no route named `{}`", name),
+            )
+        })?;
+
+        let mut used = std::collections::HashSet::new();
+        let mut segments = Vec::new();
+        for segment in template.split('/').filter(|segment| !segment.is_empty()) {
+            match segment.strip_prefix(':').or_else(|| segment.strip_prefix('*')) {
+                Some(param) => {
+                    let value = params.get(param).ok_or_else(|| {
+                        tide::Error::from_str(
+                            tide::StatusCode::BadRequest,
+                            format!("missing parameter `{}` for route `{}`", param, name),
+                        )
+                    })?;
+                    used.insert(param);
+                    segments.push((*value).to_string());
+                }
+                None => segments.push(segment.to_string()),
+            }
+        }
+
+        if let Some(extra) = params.keys().find(|param| !used.contains(*param)) {
+            return Err(tide::Error::from_str(
+                tide::StatusCode::BadRequest,
+                format!("unknown parameter `{}` for route `{}`", extra, name),
+            ));
+        }
+
+        Ok(format!("/{}", segments.join("/")))
+    }
+}
+
+impl<State: Clone + Send + Sync + 'static> Router<State> for ReverseRouter {
+    fn register_endpoint(
+        &mut self,
+        path: &str,
+        method: Option<Method>,
+        _middleware: &[Arc<dyn Middleware<State>>],
+        name: Option<&str>,
+        _endpoint: impl Endpoint<State>,
+    ) {
+        self.routes.push((path.to_string(), method));
+
+        if let Some(name) = name {
+            self.named_routes.insert(name.to_string(), path.to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+
+    #[test]
+    fn url_for_substitutes_named_segments() {
+        let mut router = ReverseRouter::new();
+        Router::<()>::register(
+            &mut router,
+            root().at("articles", |route| {
+                route.at(":id", |route| route.get(|_| async { Ok("") }).name("article.show"))
+            }),
+        )
+        .unwrap();
+
+        let url = router
+            .url_for("article.show", &[("id", "42")].iter().copied().collect())
+            .unwrap();
+
+        assert_eq!(url, "/articles/42");
+    }
+
+    #[test]
+    fn url_for_fails_on_unknown_name() {
+        let router = ReverseRouter::new();
+        assert!(router.url_for("nope", &HashMap::new()).is_err());
+    }
+
+    #[test]
+    fn url_for_fails_on_missing_or_unknown_parameter() {
+        let mut router = ReverseRouter::new();
+        Router::<()>::register(
+            &mut router,
+            root().at(":id", |route| route.get(|_| async { Ok("") }).name("show")),
+        )
+        .unwrap();
+
+        assert!(router.url_for("show", &HashMap::new()).is_err());
+        assert!(router
+            .url_for("show", &[("id", "1"), ("extra", "2")].iter().copied().collect())
+            .is_err());
+    }
+}
@@ -0,0 +1,126 @@
+//! Defines the `RouteBuilder` trait, the heart of the fluent route-building api, the
+//! `RouteBuilderExt` extension trait (implemented for every `RouteBuilder`, with no methods of
+//! its own) and the
+//! `EndpointDescriptor` that describes a single endpoint once a route tree has been flattened.
+
+use std::sync::Arc;
+
+use tide::{http::Method, Endpoint, Middleware};
+
+use crate::path::Path;
+use crate::routesegment::SubRoute;
+
+/// Describes a single endpoint collected while building a route tree: its path template, an
+/// optional method (`None` means the endpoint should be registered for all methods), the
+/// middleware collected for the scope the endpoint was defined in (outermost first), the name
+/// given to it with `.name(...)` (if any) and the endpoint itself.
+pub struct EndpointDescriptor<State> {
+    pub(crate) path: Path,
+    pub(crate) method: Option<Method>,
+    pub(crate) middleware: Vec<Arc<dyn Middleware<State>>>,
+    pub(crate) name: Option<String>,
+    pub(crate) endpoint: Box<dyn Endpoint<State>>,
+}
+
+impl<State> std::fmt::Debug for EndpointDescriptor<State> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EndpointDescriptor")
+            .field("path", &self.path)
+            .field("method", &self.method)
+            .field("middleware", &self.middleware.len())
+            .field("name", &self.name)
+            .field("endpoint", &"..")
+            .finish()
+    }
+}
+
+/// The fluent api for building a tree of routes, implemented by `RouteSegment`. Combinators
+/// consume `self` and return it again so calls can be chained into a tree.
+pub trait RouteBuilder<State: Clone + Send + Sync + 'static>: Sized {
+    /// Extend the path with a sub-path and build a sub-tree of routes under it using the
+    /// `routes` closure. `routes` is invoked exactly once, so it can take ownership of values
+    /// (like a `tide::Server` being mounted with `serve_server`) instead of having to clone them.
+    fn at(self, path: &str, routes: impl FnOnce(SubRoute<State>) -> SubRoute<State>) -> Self;
+
+    /// Apply `middleware` to a sub-tree of routes built using the `routes` closure. `routes` is
+    /// invoked exactly once, for the same reason as `at`.
+    fn with<M: Middleware<State>>(
+        self,
+        middleware: M,
+        routes: impl FnOnce(SubRoute<State>) -> SubRoute<State>,
+    ) -> Self;
+
+    /// Register `endpoint` for `method` at the current path.
+    fn method(self, method: Method, endpoint: impl Endpoint<State>) -> Self;
+
+    /// Register `endpoint` for all methods at the current path.
+    fn all(self, endpoint: impl Endpoint<State>) -> Self;
+
+    /// Register `endpoint` for a GET request at the current path.
+    fn get(self, endpoint: impl Endpoint<State>) -> Self {
+        self.method(Method::Get, endpoint)
+    }
+
+    /// Register `endpoint` for a POST request at the current path.
+    fn post(self, endpoint: impl Endpoint<State>) -> Self {
+        self.method(Method::Post, endpoint)
+    }
+
+    /// Register `endpoint` for a PUT request at the current path.
+    fn put(self, endpoint: impl Endpoint<State>) -> Self {
+        self.method(Method::Put, endpoint)
+    }
+
+    /// Register `endpoint` for a PATCH request at the current path.
+    fn patch(self, endpoint: impl Endpoint<State>) -> Self {
+        self.method(Method::Patch, endpoint)
+    }
+
+    /// Register `endpoint` for a DELETE request at the current path.
+    fn delete(self, endpoint: impl Endpoint<State>) -> Self {
+        self.method(Method::Delete, endpoint)
+    }
+
+    /// Register `endpoint` for a HEAD request at the current path.
+    fn head(self, endpoint: impl Endpoint<State>) -> Self {
+        self.method(Method::Head, endpoint)
+    }
+
+    /// Register `endpoint` for an OPTIONS request at the current path.
+    fn options(self, endpoint: impl Endpoint<State>) -> Self {
+        self.method(Method::Options, endpoint)
+    }
+
+    /// Give the endpoint just registered at the current path a name, so a `ReverseRouter` can
+    /// later render a concrete url for it with `ReverseRouter::url_for`. Call this directly after
+    /// the method combinator (`.get(endpoint).name("article.show")`) that registers the endpoint
+    /// you want to name.
+    ///
+    /// This lives on `RouteBuilder` itself rather than `RouteBuilderExt`: tagging the endpoint
+    /// just registered is implementor-specific internal state that can't be expressed in terms of
+    /// the other core combinators. Implementations must panic rather than silently drop the name
+    /// if `name` is called before any endpoint has been registered at the current path.
+    fn name(self, name: &str) -> Self;
+
+    /// Flatten the route tree into a list of `EndpointDescriptor`s.
+    fn build(self) -> Vec<EndpointDescriptor<State>>;
+
+    /// Flatten the route tree the same way `build` does, but validate it first and fail with a
+    /// descriptive `crate::Error` instead of letting conflicts (duplicate registrations, clashing
+    /// wildcard names, a catch-all that isn't in tail position) surface later as confusing
+    /// behavior inside Tide's own router.
+    fn try_build(self) -> crate::Result<Vec<EndpointDescriptor<State>>> {
+        let descriptors = self.build();
+        crate::validate::validate(&descriptors)?;
+        Ok(descriptors)
+    }
+}
+
+/// Extension trait implemented for every `RouteBuilder`. It declares no methods of its own: the
+/// verb shorthands (`get`, `post`, ...) and `try_build` are default methods of `RouteBuilder`,
+/// and `serve_dir`/`serve_file`, `serve_server` and `guard` come from the `ServeFs`, `Nest` and
+/// `Guard` traits of the `fs`, `nest` and `guard` modules. Implementing a new `RouteBuilder` only
+/// ever requires the core combinators.
+pub trait RouteBuilderExt<State: Clone + Send + Sync + 'static>: RouteBuilder<State> {}
+
+impl<State: Clone + Send + Sync + 'static, T: RouteBuilder<State>> RouteBuilderExt<State> for T {}
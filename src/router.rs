@@ -3,26 +3,57 @@
 
 use std::sync::Arc;
 
-use crate::{EndpointDescriptor, RouteSegment};
-use crate::routebuilder::RouteBuilder;
-use tide::{Endpoint, Middleware, http::Method};
+use tide::{http::Method, Endpoint, Middleware, Next, Request, Result as TideResult};
+
+use crate::routebuilder::{EndpointDescriptor, RouteBuilder};
+use crate::routesegment::RouteSegment;
 
 /// A router is any component where routes can be registered on like a tide::Server
 pub trait Router<State: Clone + Send + Sync + 'static> {
-    /// Register a single endpoint on the `Router`
+    /// Register a single endpoint on the `Router`. `name` is the name given to the endpoint with
+    /// `.name(...)`, if any, for routers that support reverse routing.
     fn register_endpoint(
         &mut self,
         path: &str,
         method: Option<Method>,
         middleware: &[Arc<dyn Middleware<State>>],
+        name: Option<&str>,
         endpoint: impl Endpoint<State>,
     );
 
-    /// Register all routes from a RouteBuilder on the `Router`
-    fn register<T: RouteBuilder<State>>(&mut self, builder: RouteSegment<State>) {
-        for EndpointDescriptor(path, method, middleware, endpoint) in builder.build() {
-            self.register_endpoint(&path, method, &middleware, endpoint)
+    /// Register all routes from a RouteBuilder on the `Router`. Fails if the route tree doesn't
+    /// pass validation, see `RouteBuilder::try_build`.
+    fn register(&mut self, builder: RouteSegment<State>) -> crate::Result<()> {
+        for EndpointDescriptor {
+            path,
+            method,
+            middleware,
+            name,
+            endpoint,
+        } in builder.try_build()?
+        {
+            self.register_endpoint(&path.to_string(), method, &middleware, name.as_deref(), endpoint)
         }
+        Ok(())
+    }
+}
+
+/// Adapts an `Arc<dyn Middleware<State>>` collected while building the route tree so it can be
+/// attached directly to a `tide::Route`, which requires an owned value implementing
+/// `Middleware` rather than a shared reference.
+#[derive(Clone)]
+struct SharedMiddleware<State>(Arc<dyn Middleware<State>>);
+
+impl<State> std::fmt::Debug for SharedMiddleware<State> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("SharedMiddleware").field(&"..").finish()
+    }
+}
+
+#[tide::utils::async_trait]
+impl<State: Clone + Send + Sync + 'static> Middleware<State> for SharedMiddleware<State> {
+    async fn handle(&self, request: Request<State>, next: Next<'_, State>) -> TideResult {
+        self.0.handle(request, next).await
     }
 }
 
@@ -31,16 +62,118 @@ impl<State: Clone + Send + Sync + 'static> Router<State> for tide::Server<State>
         &mut self,
         path: &str,
         method: Option<Method>,
-        _middleware:  &[Arc<dyn Middleware<State>>],
+        middleware: &[Arc<dyn Middleware<State>>],
+        _name: Option<&str>,
         endpoint: impl Endpoint<State>,
     ) {
-        let route = self.at(path);
-        // let endpoint = MiddlewareEndpoint::wrap_with_middleware(endpoint, &middleware);
+        let mut route = self.at(path);
+
+        // Every middleware collected for the scope this endpoint was defined in is attached to
+        // the route in order, outermost first, so it runs before anything scoped more deeply.
+        for scoped in middleware {
+            route.with(SharedMiddleware(scoped.clone()));
+        }
 
         // if method is specified then register this method, otherwise register endpoint as a catch_all
         match method {
-            Some(method) => self.at(path).method(method, endpoint),
-            None => self.at(path).all(endpoint),
-        };
+            Some(method) => {
+                route.method(method, endpoint);
+            }
+            None => {
+                route.all(endpoint);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+    use async_std::sync::Mutex;
+
+    #[derive(Clone)]
+    struct Track {
+        log: Arc<Mutex<Vec<&'static str>>>,
+        name: &'static str,
     }
-}
\ No newline at end of file
+
+    #[tide::utils::async_trait]
+    impl Middleware<()> for Track {
+        async fn handle(&self, request: Request<()>, next: Next<'_, ()>) -> TideResult {
+            self.log.lock().await.push(self.name);
+            Ok(next.run(request).await)
+        }
+    }
+
+    async fn get(server: &tide::Server<()>, path: &str) {
+        let _response: tide::http::Response = server
+            .respond(tide::http::Request::new(
+                Method::Get,
+                tide::http::Url::parse(&format!("http://example.com{}", path)).unwrap(),
+            ))
+            .await
+            .unwrap();
+    }
+
+    #[async_std::test]
+    async fn middleware_runs_outermost_scope_first() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut server = tide::Server::new();
+
+        server.register(root::<()>().with(
+            Track {
+                log: log.clone(),
+                name: "outer",
+            },
+            |route| {
+                route.with(
+                    Track {
+                        log: log.clone(),
+                        name: "inner",
+                    },
+                    |route| route.get(|_| async { Ok("") }),
+                )
+            },
+        ))
+        .unwrap();
+
+        get(&server, "/").await;
+
+        assert_eq!(*log.lock().await, vec!["outer", "inner"]);
+    }
+
+    #[async_std::test]
+    async fn inner_scopes_see_the_union_of_their_ancestors_middleware() {
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let mut server = tide::Server::new();
+
+        server.register(root::<()>().with(
+            Track {
+                log: log.clone(),
+                name: "outer",
+            },
+            |route| {
+                route
+                    .get(|_| async { Ok("") })
+                    .at("inner", |route| {
+                        route.with(
+                            Track {
+                                log: log.clone(),
+                                name: "inner",
+                            },
+                            |route| route.get(|_| async { Ok("") }),
+                        )
+                    })
+            },
+        ))
+        .unwrap();
+
+        get(&server, "/").await;
+        assert_eq!(*log.lock().await, vec!["outer"]);
+
+        log.lock().await.clear();
+        get(&server, "/inner").await;
+        assert_eq!(*log.lock().await, vec!["outer", "inner"]);
+    }
+}
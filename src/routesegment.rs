@@ -0,0 +1,119 @@
+//! `RouteSegment` is the core building block of the route tree. Each segment owns a path
+//! template, the middleware collected for its scope, any endpoints registered directly on it and
+//! its child segments; `build` flattens the tree into a list of `EndpointDescriptor`s.
+
+use std::sync::Arc;
+
+use tide::{http::Method, Endpoint, Middleware};
+
+use crate::path::Path;
+use crate::routebuilder::{EndpointDescriptor, RouteBuilder};
+
+/// The endpoints registered directly on a `RouteSegment`: method (`None` for a catch-all), the
+/// endpoint itself and the name given to it with `.name(...)`, if any.
+type Endpoints<State> = Vec<(Option<Method>, Box<dyn Endpoint<State>>, Option<String>)>;
+
+/// A segment of the route tree. Build one up with `root()` and the combinators on
+/// `RouteBuilder`/`RouteBuilderExt`.
+pub struct RouteSegment<State> {
+    path: Path,
+    middleware: Vec<Arc<dyn Middleware<State>>>,
+    endpoints: Endpoints<State>,
+    children: Vec<RouteSegment<State>>,
+}
+
+impl<State> std::fmt::Debug for RouteSegment<State> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouteSegment")
+            .field("path", &self.path)
+            .field("middleware", &self.middleware.len())
+            .field("endpoints", &self.endpoints.len())
+            .field("children", &self.children)
+            .finish()
+    }
+}
+
+/// Alias used for the `RouteSegment` passed into `at`/`with` closures, so the fluent api reads
+/// naturally as building a sub-route of the tree.
+pub type SubRoute<State> = RouteSegment<State>;
+
+/// Start building a route tree from the root path.
+pub fn root<State: Clone + Send + Sync + 'static>() -> RouteSegment<State> {
+    RouteSegment::new(Path::new())
+}
+
+impl<State: Clone + Send + Sync + 'static> RouteSegment<State> {
+    fn new(path: Path) -> Self {
+        Self {
+            path,
+            middleware: Vec::new(),
+            endpoints: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn build_into(self, inherited: &[Arc<dyn Middleware<State>>], out: &mut Vec<EndpointDescriptor<State>>) {
+        let middleware: Vec<_> = inherited.iter().cloned().chain(self.middleware).collect();
+
+        for (method, endpoint, name) in self.endpoints {
+            out.push(EndpointDescriptor {
+                path: self.path.clone(),
+                method,
+                middleware: middleware.clone(),
+                name,
+                endpoint,
+            });
+        }
+
+        for child in self.children {
+            child.build_into(&middleware, out);
+        }
+    }
+}
+
+impl<State: Clone + Send + Sync + 'static> RouteBuilder<State> for RouteSegment<State> {
+    fn at(mut self, path: &str, routes: impl FnOnce(SubRoute<State>) -> SubRoute<State>) -> Self {
+        let child = routes(RouteSegment::new(self.path.append(path)));
+        self.children.push(child);
+        self
+    }
+
+    fn with<M: Middleware<State>>(
+        mut self,
+        middleware: M,
+        routes: impl FnOnce(SubRoute<State>) -> SubRoute<State>,
+    ) -> Self {
+        let mut child = RouteSegment::new(self.path.clone());
+        child.middleware.push(Arc::new(middleware));
+        self.children.push(routes(child));
+        self
+    }
+
+    fn method(mut self, method: Method, endpoint: impl Endpoint<State>) -> Self {
+        self.endpoints.push((Some(method), Box::new(endpoint), None));
+        self
+    }
+
+    fn all(mut self, endpoint: impl Endpoint<State>) -> Self {
+        self.endpoints.push((None, Box::new(endpoint), None));
+        self
+    }
+
+    fn name(mut self, name: &str) -> Self {
+        let endpoint = self.endpoints.last_mut().unwrap_or_else(|| {
+            panic!(
+                "`.name(\"{}\")` must be called directly after a method combinator (.get/.post/\
+                 .all/etc) that registers the endpoint to name",
+                name
+            )
+        });
+        endpoint.2 = Some(name.to_string());
+        self
+    }
+
+    fn build(self) -> Vec<EndpointDescriptor<State>> {
+        let mut descriptors = Vec::new();
+        self.build_into(&[], &mut descriptors);
+        descriptors
+    }
+}
@@ -0,0 +1,112 @@
+//! Build-time validation of a flattened route tree. Tide's own router only finds out about typos
+//! and conflicts (two sibling branches using `:id` and `:slug` at the same position, a catch-all
+//! that isn't in tail position, or the same method registered twice for the same path) at request
+//! time, as confusing runtime behavior. Validating the flattened `EndpointDescriptor`s up front
+//! lets `try_build`/`register` fail fast with a descriptive error instead.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+
+use crate::routebuilder::EndpointDescriptor;
+use crate::Result;
+
+pub(crate) fn validate<State>(descriptors: &[EndpointDescriptor<State>]) -> Result<()> {
+    let mut wildcard_names: HashMap<String, String> = HashMap::new();
+    let mut registrations = HashMap::new();
+
+    for descriptor in descriptors {
+        let segments = descriptor.path.segments();
+
+        for (index, segment) in segments.iter().enumerate() {
+            let (param, is_catch_all) = if let Some(param) = segment.strip_prefix('*') {
+                (param, true)
+            } else if let Some(param) = segment.strip_prefix(':') {
+                (param, false)
+            } else {
+                continue;
+            };
+
+            if is_catch_all && index != segments.len() - 1 {
+                return Err(error(format!(
+                    "catch-all segment `{}` in path `{}` must be the last segment of the path",
+                    segment, descriptor.path
+                )));
+            }
+
+            let param = param.to_string();
+            let prefix = segments[..index].join("/");
+            match wildcard_names.entry(prefix.clone()) {
+                Entry::Occupied(existing) if *existing.get() != param => {
+                    return Err(error(format!(
+                        "conflicting parameter names `:{}` and `:{}` at the same position under `/{}`",
+                        existing.get(),
+                        param,
+                        prefix
+                    )));
+                }
+                Entry::Vacant(slot) => {
+                    slot.insert(param);
+                }
+                _ => {}
+            }
+        }
+
+        let key = (descriptor.path.to_string(), descriptor.method);
+        if registrations.insert(key.clone(), ()).is_some() {
+            let (path, method) = key;
+            return Err(error(match method {
+                Some(method) => format!("`{}` is already registered for `{}`", method, path),
+                None => format!("a catch-all endpoint is already registered for `{}`", path),
+            }));
+        }
+    }
+
+    Ok(())
+}
+
+fn error(message: String) -> tide::Error {
+    tide::Error::from_str(tide::StatusCode::InternalServerError, message)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+
+    #[test]
+    fn rejects_conflicting_wildcard_names() {
+        let routes = root::<()>()
+            .at(":id", |route| route.get(|_| async { Ok("") }))
+            .at(":slug", |route| route.get(|_| async { Ok("") }));
+
+        assert!(routes.try_build().is_err());
+    }
+
+    #[test]
+    fn rejects_catch_all_not_in_tail_position() {
+        let routes = root::<()>().at("*rest/more", |route| route.get(|_| async { Ok("") }));
+
+        assert!(routes.try_build().is_err());
+    }
+
+    #[test]
+    fn rejects_duplicate_method_registrations() {
+        let routes = root::<()>()
+            .at("articles", |route| route.get(|_| async { Ok("") }))
+            .at("articles", |route| route.get(|_| async { Ok("") }));
+
+        assert!(routes.try_build().is_err());
+    }
+
+    #[test]
+    fn accepts_a_well_formed_tree() {
+        let routes = root::<()>()
+            .at("articles", |route| {
+                route
+                    .get(|_| async { Ok("") })
+                    .at(":id", |route| route.get(|_| async { Ok("") }))
+            })
+            .at("files", |route| route.get(|_| async { Ok("") }));
+
+        assert!(routes.try_build().is_ok());
+    }
+}